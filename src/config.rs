@@ -0,0 +1,228 @@
+use crossterm::{
+    event::{KeyCode, KeyModifiers},
+    style::Color,
+};
+use directories::ProjectDirs;
+use serde::Deserialize;
+use std::{collections::HashMap, fs};
+
+/// A key chord: a code plus the modifiers that must be held for it to
+/// match. Matching is exact, mirroring the `==` checks the keymap replaces.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct Key {
+    pub code: KeyCode,
+    pub modifiers: KeyModifiers,
+}
+
+impl Key {
+    pub fn new(code: KeyCode, modifiers: KeyModifiers) -> Self {
+        Self { code, modifiers }
+    }
+
+    fn plain(c: char) -> Self {
+        Self::new(KeyCode::Char(c), KeyModifiers::NONE)
+    }
+
+    fn ctrl(c: char) -> Self {
+        Self::new(KeyCode::Char(c), KeyModifiers::CONTROL)
+    }
+}
+
+/// Actions a key chord can be bound to. `Quit` through `PreviousSettings`
+/// are only meaningful on the mode-select screen; `Exit` through `Backspace`
+/// are only meaningful in the typing loop, where any other key (unbound, or bound
+/// to a mode-select action) falls back to typing the character.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Deserialize)]
+pub enum Action {
+    Quit,
+    SingleQuote,
+    MultiQuote,
+    TimedMode,
+    ZenMode,
+    PreviousSettings,
+    Exit,
+    DeleteWord,
+    DeleteLine,
+    Backspace,
+}
+
+impl Action {
+    /// Whether this action belongs to the typing-loop keymap rather than
+    /// the mode-select keymap.
+    fn is_typing_action(self) -> bool {
+        matches!(
+            self,
+            Action::Exit | Action::DeleteWord | Action::DeleteLine | Action::Backspace
+        )
+    }
+}
+
+/// The colors used to render typed characters against the quote.
+#[derive(Clone, Copy)]
+pub struct Theme {
+    pub correct: Color,
+    pub incorrect: Color,
+    pub cursor: Color,
+    pub pending: Color,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self {
+            correct: Color::Green,
+            incorrect: Color::Red,
+            cursor: Color::Reset,
+            pending: Color::Reset,
+        }
+    }
+}
+
+#[derive(Deserialize, Default)]
+struct RawTheme {
+    correct: Option<String>,
+    incorrect: Option<String>,
+    cursor: Option<String>,
+    pending: Option<String>,
+}
+
+#[derive(Deserialize, Default)]
+struct RawConfig {
+    keys: Option<HashMap<String, Action>>,
+    theme: Option<RawTheme>,
+}
+
+pub struct Config {
+    /// Keymap for the mode-select screen (`get_session_type`).
+    pub session_keymap: HashMap<Key, Action>,
+    /// Keymap for the typing loop (`TypingState::on_key_event`). Kept
+    /// separate from `session_keymap` so that letters like `s`/`m`/`t`/`z`/
+    /// `q`, which pick a session mode, still type normally mid-quote.
+    pub typing_keymap: HashMap<Key, Action>,
+    pub theme: Theme,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            session_keymap: default_session_keymap(),
+            typing_keymap: default_typing_keymap(),
+            theme: Theme::default(),
+        }
+    }
+}
+
+fn default_session_keymap() -> HashMap<Key, Action> {
+    let mut map = HashMap::new();
+    map.insert(Key::plain('s'), Action::SingleQuote);
+    map.insert(Key::plain('m'), Action::MultiQuote);
+    map.insert(Key::plain('t'), Action::TimedMode);
+    map.insert(Key::plain('z'), Action::ZenMode);
+    map.insert(Key::plain('q'), Action::Quit);
+    map.insert(Key::plain('c'), Action::Quit);
+    map.insert(
+        Key::new(KeyCode::Enter, KeyModifiers::NONE),
+        Action::PreviousSettings,
+    );
+    map
+}
+
+fn default_typing_keymap() -> HashMap<Key, Action> {
+    let mut map = HashMap::new();
+    map.insert(Key::ctrl('c'), Action::Exit);
+    map.insert(Key::ctrl('w'), Action::DeleteWord);
+    map.insert(Key::ctrl('u'), Action::DeleteLine);
+    map.insert(
+        Key::new(KeyCode::Backspace, KeyModifiers::CONTROL),
+        Action::DeleteWord,
+    );
+    map.insert(
+        Key::new(KeyCode::Backspace, KeyModifiers::NONE),
+        Action::Backspace,
+    );
+    map
+}
+
+/// Loads `config.toml` from the platform config dir, falling back to the
+/// built-in keymap and theme for anything missing or if the file, or the
+/// config dir itself, doesn't exist.
+pub fn load() -> Config {
+    let mut config = Config::default();
+
+    let Some(dirs) = ProjectDirs::from("", "", "quote_typer") else {
+        return config;
+    };
+    let Ok(contents) = fs::read_to_string(dirs.config_dir().join("config.toml")) else {
+        return config;
+    };
+    let Ok(raw) = toml::from_str::<RawConfig>(&contents) else {
+        return config;
+    };
+
+    if let Some(keys) = raw.keys {
+        for (chord, action) in keys {
+            if let Some(key) = parse_key(&chord) {
+                if action.is_typing_action() {
+                    config.typing_keymap.insert(key, action);
+                } else {
+                    config.session_keymap.insert(key, action);
+                }
+            }
+        }
+    }
+
+    if let Some(theme) = raw.theme {
+        apply_theme(&mut config.theme, theme);
+    }
+
+    config
+}
+
+fn apply_theme(theme: &mut Theme, raw: RawTheme) {
+    if let Some(c) = raw.correct.and_then(|s| parse_color(&s)) {
+        theme.correct = c;
+    }
+    if let Some(c) = raw.incorrect.and_then(|s| parse_color(&s)) {
+        theme.incorrect = c;
+    }
+    if let Some(c) = raw.cursor.and_then(|s| parse_color(&s)) {
+        theme.cursor = c;
+    }
+    if let Some(c) = raw.pending.and_then(|s| parse_color(&s)) {
+        theme.pending = c;
+    }
+}
+
+/// Parses chord strings like `"ctrl+w"` or `"q"` from the config file.
+fn parse_key(chord: &str) -> Option<Key> {
+    let mut modifiers = KeyModifiers::NONE;
+    let mut code = None;
+    for part in chord.split('+') {
+        match part.to_ascii_lowercase().as_str() {
+            "ctrl" => modifiers |= KeyModifiers::CONTROL,
+            "shift" => modifiers |= KeyModifiers::SHIFT,
+            "alt" => modifiers |= KeyModifiers::ALT,
+            "enter" => code = Some(KeyCode::Enter),
+            "backspace" => code = Some(KeyCode::Backspace),
+            "esc" | "escape" => code = Some(KeyCode::Esc),
+            c if c.chars().count() == 1 => code = Some(KeyCode::Char(c.chars().next().unwrap())),
+            _ => return None,
+        }
+    }
+    Some(Key::new(code?, modifiers))
+}
+
+fn parse_color(name: &str) -> Option<Color> {
+    match name.to_ascii_lowercase().as_str() {
+        "black" => Some(Color::Black),
+        "red" => Some(Color::Red),
+        "green" => Some(Color::Green),
+        "yellow" => Some(Color::Yellow),
+        "blue" => Some(Color::Blue),
+        "magenta" => Some(Color::Magenta),
+        "cyan" => Some(Color::Cyan),
+        "white" => Some(Color::White),
+        "grey" | "gray" => Some(Color::Grey),
+        "reset" => Some(Color::Reset),
+        _ => None,
+    }
+}
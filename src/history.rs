@@ -0,0 +1,99 @@
+use crate::{typing::SessionType, Stats};
+use directories::ProjectDirs;
+use serde::{Deserialize, Serialize};
+use std::{
+    fs::{self, OpenOptions},
+    io::{self, BufRead, Write},
+    path::PathBuf,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+/// How many of the most recent sessions the rolling average is taken over.
+const ROLLING_WINDOW: usize = 10;
+
+/// One completed session, appended as a line of JSON to the history file.
+#[derive(Serialize, Deserialize)]
+pub struct SessionRecord {
+    pub timestamp: u64,
+    pub session_type: String,
+    pub wpm: f32,
+    pub cpm: f32,
+    pub accuracy: f32,
+    pub num_chars: u32,
+    pub elapsed_time: f32,
+}
+
+fn history_path() -> Option<PathBuf> {
+    let dirs = ProjectDirs::from("", "", "quote_typer")?;
+    Some(dirs.data_dir().join("history.jsonl"))
+}
+
+/// Appends `stats` as a new record in the history file, creating the data
+/// directory and file on first run.
+pub(crate) fn record_session(stats: &Stats, session_type: SessionType) -> io::Result<()> {
+    let Some(path) = history_path() else {
+        return Ok(());
+    };
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    let record = SessionRecord {
+        timestamp: SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0),
+        session_type: session_type.label().to_string(),
+        wpm: stats.wpm(),
+        cpm: stats.cpm(),
+        accuracy: stats.num_correct as f32 / stats.num_chars_typed.max(1) as f32,
+        num_chars: stats.num_chars_typed,
+        elapsed_time: stats.elapsed_time,
+    };
+
+    let line = serde_json::to_string(&record).map_err(io::Error::other)?;
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    writeln!(file, "{line}")
+}
+
+/// Loads every previously recorded session, skipping any line that fails to
+/// parse rather than aborting startup.
+pub fn load_history() -> Vec<SessionRecord> {
+    let Some(path) = history_path() else {
+        return Vec::new();
+    };
+    let Ok(file) = fs::File::open(path) else {
+        return Vec::new();
+    };
+
+    io::BufReader::new(file)
+        .lines()
+        .map_while(Result::ok)
+        .filter_map(|line| serde_json::from_str::<SessionRecord>(&line).ok())
+        .collect()
+}
+
+/// Builds a short summary of how `current_wpm` compares to history: the
+/// all-time best WPM, the rolling average over the last `ROLLING_WINDOW`
+/// sessions, and whether this run set a new record. `records` is assumed to
+/// already include the just-completed session. Returns an empty string if
+/// `records` is empty (e.g. the platform data dir couldn't be found, or
+/// `record_session` failed), since there's nothing yet to compare against.
+pub fn summary(current_wpm: f32, records: &[SessionRecord]) -> String {
+    if records.is_empty() {
+        return String::new();
+    }
+
+    let best = records.iter().map(|r| r.wpm).fold(f32::MIN, f32::max);
+    let recent = &records[records.len().saturating_sub(ROLLING_WINDOW)..];
+    let rolling_avg = recent.iter().map(|r| r.wpm).sum::<f32>() / recent.len() as f32;
+    let is_record = current_wpm >= best;
+
+    format!(
+        "Best WPM: {:.1}{}\nRolling Avg WPM (last {}): {:.1}",
+        best,
+        if is_record { " (new record!)" } else { "" },
+        ROLLING_WINDOW,
+        rolling_avg
+    )
+}
@@ -1,7 +1,10 @@
+pub mod config;
+pub mod history;
 pub mod quote;
 pub mod typing;
 
 use colored::*;
+use config::Theme;
 use crossterm::{cursor, execute, queue, style, terminal};
 use log::debug;
 use quote::Quote;
@@ -11,6 +14,7 @@ use std::{
     iter::zip,
 };
 use typing::SessionType;
+use unicode_width::UnicodeWidthChar;
 
 #[derive(Copy, Clone)]
 struct Stats {
@@ -52,14 +56,22 @@ impl Stats {
         self.elapsed_time = elapsed_time;
     }
 
+    fn cpm(&self) -> f32 {
+        60.0 * self.num_correct as f32 / self.elapsed_time
+    }
+
+    fn wpm(&self) -> f32 {
+        self.cpm() / 5.0
+    }
+
     fn analysis_str(&self, extra: &str) -> String {
         let total_str = self.num_chars_typed.to_string().color(Color::Blue);
         let num_correct_str = self.num_correct.to_string().color(Color::Green);
         let mistakes_str = (self.num_chars_typed - self.num_correct)
             .to_string()
             .color(Color::Red);
-        let cpm = 60.0 * self.num_correct as f32 / self.elapsed_time;
-        let wpm = cpm / 5.0;
+        let cpm = self.cpm();
+        let wpm = self.wpm();
         format!(
 r#"Total: {}, Correct: {}, Mistakes: {}
 Elapsed Time: {}
@@ -101,11 +113,11 @@ struct ColoredChar {
 }
 
 impl ColoredChar {
-    fn new(typed_char: &char, quote_char: &char) -> Self {
+    fn new(typed_char: &char, quote_char: &char, theme: &Theme) -> Self {
         let color = if typed_char == quote_char {
-            style::Color::Green
+            theme.correct
         } else {
-            style::Color::Red
+            theme.incorrect
         };
 
         // overrides
@@ -141,10 +153,12 @@ impl Cursor {
         })
     }
 
-    fn align_center(&mut self, out: &mut Stdout, num_chars: u32) -> io::Result<()> {
-        debug!("{:?}, {:?}", num_chars, self.num_cols);
-        let cursor_col = num_chars as u16 % self.num_cols;
-        let mut cursor_row = num_chars as u16 / self.num_cols;
+    fn align_center(&mut self, out: &mut Stdout, typed_chars: &[char]) -> io::Result<()> {
+        debug!("{:?}, {:?}", typed_chars.len(), self.num_cols);
+        let (mut cursor_col, mut cursor_row) = (0u16, 0u16);
+        for &c in typed_chars {
+            advance_cursor(&mut cursor_col, &mut cursor_row, self.num_cols, c);
+        }
 
         if cursor_row > self.num_rows / 2 {
             cursor_row = self.num_rows / 2;
@@ -163,17 +177,21 @@ impl Cursor {
         )
     }
 
-    fn write_before(&self, out: &mut Stdout, chars: &[ColoredChar]) -> io::Result<()> {
-        let mut cursor = self.clone();
-
-        for c in chars {
-            if cursor.cursor_back_one().is_err() {
-                break;
-            };
-
+    /// Redraws already-typed text in its correct/incorrect color. `chars`
+    /// and `positions` are parallel: `positions[i]` is where `chars[i]`
+    /// starts, precomputed by a single forward walk (`char_positions`) so
+    /// row wraps caused by earlier wide glyphs are accounted for exactly —
+    /// a backward per-char replay can't recover that padding on its own.
+    fn write_before(
+        &self,
+        out: &mut Stdout,
+        chars: &[ColoredChar],
+        positions: &[(u16, u16)],
+    ) -> io::Result<()> {
+        for (c, &(col, row)) in chars.iter().zip(positions) {
             queue!(
                 out,
-                cursor::MoveTo(cursor.col, cursor.row),
+                cursor::MoveTo(col, row),
                 style::SetForegroundColor(c.color),
                 style::Print(c.character)
             )?;
@@ -181,16 +199,18 @@ impl Cursor {
         queue!(out, cursor::MoveTo(self.col, self.row))
     }
 
-    fn write_after(&self, out: &mut Stdout, chars: &[char]) -> io::Result<()> {
+    fn write_after(&self, out: &mut Stdout, chars: &[char], theme: &Theme) -> io::Result<()> {
         let mut cursor = self.clone();
-        queue!(out, style::SetForegroundColor(style::Color::Reset))?;
 
-        for c in chars {
-            if cursor.cursor_forward_one().is_err() {
+        for (i, c) in chars.iter().enumerate() {
+            if cursor.cursor_forward_one(char_width(*c)).is_err() {
                 break;
             };
 
-            queue!(out, style::Print(c))?;
+            // The very next char to type is highlighted as the cursor;
+            // everything after it is still pending.
+            let color = if i == 0 { theme.cursor } else { theme.pending };
+            queue!(out, style::SetForegroundColor(color), style::Print(c))?;
         }
         let clear_type = terminal::ClearType::UntilNewLine;
         queue!(
@@ -201,33 +221,63 @@ impl Cursor {
         )
     }
 
-    fn cursor_back_one(&mut self) -> Result<(), ()> {
-        if self.col == 0 && self.row == 0 {
-            // cannot move back one if at 0, 0
-            return Err(());
-        } else if self.col == 0 {
-            self.row -= 1;
-            self.col = self.num_cols - 1;
-        } else {
-            self.col -= 1;
+    /// Steps the cursor forward by `width` columns, padding to the start of
+    /// the next row when a wide glyph would straddle the right edge. Zero
+    /// width (combining marks) leaves the cursor where it is.
+    fn cursor_forward_one(&mut self, width: u16) -> Result<(), ()> {
+        if width == 0 {
+            return Ok(());
         }
-        Ok(())
-    }
-
-    fn cursor_forward_one(&mut self) -> Result<(), ()> {
-        if self.col == self.num_cols - 1 && self.row == self.num_rows - 1 {
-            // cannot move back one if at 0, 0
-            return Err(());
-        } else if self.col == self.num_cols - 1 {
+        if self.col + width > self.num_cols {
+            if self.row + 1 >= self.num_rows {
+                return Err(());
+            }
             self.row += 1;
-            self.col = 0;
+            self.col = width;
         } else {
-            self.col += 1;
+            self.col += width;
         }
         Ok(())
     }
 }
 
+/// The on-screen width of `c`: 0 for combining marks and control
+/// characters, 2 for wide East-Asian/emoji glyphs, 1 otherwise.
+fn char_width(c: char) -> u16 {
+    c.width().unwrap_or(0) as u16
+}
+
+/// Advances a virtual (col, row) cursor past `c` within a terminal
+/// `num_cols` wide, wrapping to the next row when the line is full and
+/// pushing wide glyphs that would straddle the right edge onto the next
+/// line, mirroring `Cursor::cursor_forward_one`.
+fn advance_cursor(col: &mut u16, row: &mut u16, num_cols: u16, c: char) {
+    let width = char_width(c);
+    if width == 0 {
+        return;
+    }
+    if *col + width > num_cols {
+        *col = 0;
+        *row += 1;
+    }
+    *col += width;
+}
+
+/// The starting (col, row) of each char in `chars`, computed by walking
+/// forward from (0, 0) a single time. Unlike replaying backward from the
+/// cursor one char at a time, this correctly carries how much a row wrap
+/// actually padded out the previous row.
+fn char_positions(num_cols: u16, chars: &[char]) -> Vec<(u16, u16)> {
+    let (mut col, mut row) = (0u16, 0u16);
+    chars
+        .iter()
+        .map(|&c| {
+            advance_cursor(&mut col, &mut row, num_cols, c);
+            (col - char_width(c), row)
+        })
+        .collect()
+}
+
 pub fn initialize_session(out: &mut Stdout) -> Result<(), std::io::Error> {
     execute!(
         out,
@@ -250,20 +300,33 @@ fn write_to_terminal(
     quote_chars: &[char],
     typed_chars: &[char],
     stats: Option<Stats>,
+    theme: &Theme,
 ) -> std::io::Result<()> {
     let mut cursor = Cursor::new()?;
     if let Some(stats) = stats {
         cursor.print_stats(out, stats)?;
     }
-    cursor.align_center(out, typed_chars.len() as u32)?;
+    cursor.align_center(out, typed_chars)?;
     let before_cursor: Vec<ColoredChar> = zip(typed_chars.iter(), quote_chars.iter())
-        .map(|(t, q)| ColoredChar::new(t, q))
-        .rev()
+        .map(|(t, q)| ColoredChar::new(t, q, theme))
+        .collect();
+    let positions = char_positions(cursor.num_cols, typed_chars);
+    // `align_center` caps the cursor row at half the screen; scroll the typed
+    // text up by the rows it capped, dropping those now above the top.
+    let (mut end_col, mut end_row) = (0u16, 0u16);
+    for &c in typed_chars {
+        advance_cursor(&mut end_col, &mut end_row, cursor.num_cols, c);
+    }
+    let scroll = end_row - cursor.row;
+    let skip = positions.iter().take_while(|&&(_, row)| row < scroll).count();
+    let positions: Vec<(u16, u16)> = positions[skip..]
+        .iter()
+        .map(|&(col, row)| (col, row - scroll))
         .collect();
 
     let after_cursor = &quote_chars[typed_chars.len()..];
-    cursor.write_before(out, &before_cursor)?;
-    cursor.write_after(out, after_cursor)?;
+    cursor.write_before(out, &before_cursor[skip..], &positions)?;
+    cursor.write_after(out, after_cursor, theme)?;
     out.flush()
 }
 
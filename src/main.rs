@@ -1,5 +1,6 @@
 use colored::*;
 use quote_typer::{
+    config,
     initialize_session,
     typing::{typing_session, SessionType},
 };
@@ -40,15 +41,15 @@ fn print_analysis(quote_chars: &[char], typed_chars: &[char], elapsed_time: Dura
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let log_file = File::create("log.txt")?;
-    let mut log_file2 = File::create("log2.txt")?;
     env_logger::builder()
         .target(env_logger::Target::Pipe(Box::new(log_file)))
         .filter_level(log::LevelFilter::Debug)
         .init();
     let mut out = std::io::stdout();
+    let config = config::load();
     initialize_session(&mut out)?;
     loop {
-        typing_session(SessionType::MultiQuote(4), &mut out, &mut log_file2).await?;
+        typing_session(SessionType::MultiQuote(4), &mut out, &config).await?;
         // print_analysis(&quote.content_chars(), &typed_chars, start.elapsed());
 
         // if !true_on_enter(&mut out) {
@@ -18,6 +18,17 @@ impl Quote {
     pub fn content_chars(&self) -> Vec<char> {
         self.content.chars().collect()
     }
+
+    /// The author's name wrapped in an OSC 8 hyperlink pointing at their
+    /// Wikipedia page, falling back to plain text when the terminal
+    /// doesn't advertise hyperlink support.
+    pub fn author_link(&self) -> String {
+        let url = format!(
+            "https://en.wikipedia.org/wiki/{}",
+            self.author.replace(' ', "_")
+        );
+        hyperlink(&self.author, &url)
+    }
 }
 
 pub async fn get_quote() -> Result<Quote, reqwest::Error> {
@@ -26,3 +37,19 @@ pub async fn get_quote() -> Result<Quote, reqwest::Error> {
         .json::<Quote>()
         .await
 }
+
+/// Best-effort check for OSC 8 hyperlink support: terminals that report no
+/// `TERM`, or the `dumb` terminal, are assumed not to support it.
+fn supports_hyperlinks() -> bool {
+    !matches!(std::env::var("TERM").as_deref(), Ok("dumb") | Err(_))
+}
+
+/// Wraps `text` in an OSC 8 escape sequence linking to `url`, or returns it
+/// unchanged when `supports_hyperlinks` is false.
+fn hyperlink(text: &str, url: &str) -> String {
+    if supports_hyperlinks() {
+        format!("\x1b]8;;{url}\x1b\\{text}\x1b]8;;\x1b\\")
+    } else {
+        text.to_string()
+    }
+}
@@ -2,20 +2,37 @@
 use std::{
     fs::File,
     io::{self, Stdout, Write},
-    time::Instant,
+    time::{Duration, Instant},
 };
 
 use crossterm::{
     cursor,
-    event::{read, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers},
+    event::{read, Event as CrosstermEvent, KeyCode, KeyEvent, KeyEventKind, KeyModifiers},
     queue,
     style::{self, style},
     terminal,
 };
+use tokio::sync::mpsc;
 
-use crate::{quote::get_quote, terminate_session, write_to_terminal, Quote, Stats, get_number_input, clear_screen_and_print};
+use crate::{
+    config::{Action, Config, Key},
+    history,
+    quote::get_quote, terminate_session, write_to_terminal, Quote, Stats, get_number_input, clear_screen_and_print,
+};
 use log::debug;
 
+/// How often an `Event::Tick` is sent while a session is running, so the
+/// clock and stats advance even when the user isn't pressing keys.
+const TICK_INTERVAL: Duration = Duration::from_millis(200);
+
+/// Everything the `typing_session` loop reacts to: a keystroke, a tick of
+/// the clock, or a quote that finished fetching in the background.
+enum Event {
+    Key(KeyEvent),
+    Tick,
+    QuoteReady(Quote),
+}
+
 /// The the type of typing test
 #[derive(Copy, Clone)]
 pub enum SessionType {
@@ -29,6 +46,18 @@ pub enum SessionType {
     Zen,
 }
 
+impl SessionType {
+    /// Short discriminant name used when persisting session history.
+    pub fn label(&self) -> &'static str {
+        match self {
+            SessionType::SingleQuote => "single_quote",
+            SessionType::MultiQuote(_) => "multi_quote",
+            SessionType::Time(_) => "time",
+            SessionType::Zen => "zen",
+        }
+    }
+}
+
 pub enum SessionOptions {
     StatsOn,
     StatsOff,
@@ -46,23 +75,28 @@ enter: Previous Settings (Defaults to Single Quote)
     q: Quit
 ";
 
-pub fn get_session_type(out: &mut Stdout, previous: SessionType) -> io::Result<Option<SessionType>> {
+pub fn get_session_type(
+    out: &mut Stdout,
+    previous: SessionType,
+    config: &Config,
+) -> io::Result<Option<SessionType>> {
     loop {
         clear_screen_and_print(out, SESSION_REQUEST_INFO, true)?;
-        let Event::Key(key_event) = read()? else {
+        let CrosstermEvent::Key(key_event) = read()? else {
             continue;
         };
 
-        let session_type = match key_event.code {
-            KeyCode::Enter => previous,
-            KeyCode::Char('q') | KeyCode::Char('c') => return Ok(None),
-            KeyCode::Char('s') => SessionType::SingleQuote,
-            KeyCode::Char('z') => SessionType::Zen,
-            KeyCode::Char('m') => {
+        let key = Key::new(key_event.code, key_event.modifiers);
+        let session_type = match config.session_keymap.get(&key) {
+            Some(Action::PreviousSettings) => previous,
+            Some(Action::Quit) => return Ok(None),
+            Some(Action::SingleQuote) => SessionType::SingleQuote,
+            Some(Action::ZenMode) => SessionType::Zen,
+            Some(Action::MultiQuote) => {
                 let num = get_number_input(out)?;
                 SessionType::MultiQuote(num)
             }
-            KeyCode::Char('t') => {
+            Some(Action::TimedMode) => {
                 let num = get_number_input(out)?;
                 SessionType::Time(num)
             }
@@ -76,49 +110,88 @@ pub fn get_session_type(out: &mut Stdout, previous: SessionType) -> io::Result<O
 pub async fn typing_session(
     session_type: SessionType,
     out: &mut Stdout,
+    config: &Config,
 ) -> Result<(), Box<dyn std::error::Error>> {
     clear_screen_and_print(out, "", false)?;
-    let mut state = TypingState::new(session_type, out, get_quote().await?);
+    let mut state = TypingState::new(session_type, out, get_quote().await?, config);
     state.print_to_terminal()?;
-    let mut next_quote = None;
 
-    loop {
-        let key_event = match read()? {
-            Event::Key(key) => key,
-            _ => continue,
-        };
+    let (event_tx, mut event_rx) = mpsc::unbounded_channel();
 
-        match state.on_key_event(key_event)? {
-            ControlFlow::Normal => (),
-            ControlFlow::RequestsQuote => {
-                next_quote = Some(tokio::spawn(get_quote()));
-                state.getting_next_quote();
-            }
-            ControlFlow::WaitingForQuote => {
-                if let Some(future_quote) = next_quote.take() {
-                    state.add_quote(future_quote.await??);
-                }
-            }
-            ControlFlow::Finished => {
+    // Forward crossterm key events onto the channel from a blocking task,
+    // since `read()` blocks the thread until a key is pressed.
+    let key_tx = event_tx.clone();
+    tokio::task::spawn_blocking(move || loop {
+        let event = match read() {
+            Ok(CrosstermEvent::Key(key)) => Event::Key(key),
+            Ok(_) => continue,
+            Err(_) => break,
+        };
+        if key_tx.send(event).is_err() {
+            break;
+        }
+    });
+
+    // Keep the clock and stats advancing even while the user isn't typing.
+    let tick_tx = event_tx.clone();
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(TICK_INTERVAL);
+        loop {
+            ticker.tick().await;
+            if tick_tx.send(Event::Tick).is_err() {
                 break;
             }
-            ControlFlow::Exit => {
-                terminate_session(out)?;
-                println!("User exited program");
-                std::process::exit(130);
-            }
-        };
+        }
+    });
+
+    while let Some(event) = event_rx.recv().await {
+        match event {
+            Event::Key(key_event) => match state.on_key_event(key_event)? {
+                ControlFlow::RequestsQuote => {
+                    state.getting_next_quote();
+                    let quote_tx = event_tx.clone();
+                    tokio::spawn(async move {
+                        if let Ok(quote) = get_quote().await {
+                            let _ = quote_tx.send(Event::QuoteReady(quote));
+                        }
+                    });
+                }
+                ControlFlow::Exit => {
+                    terminate_session(out)?;
+                    println!("User exited program");
+                    std::process::exit(130);
+                }
+                ControlFlow::Normal | ControlFlow::WaitingForQuote | ControlFlow::Finished => (),
+            },
+            Event::QuoteReady(quote) => state.add_quote(quote),
+            Event::Tick => (),
+        }
 
+        if state.update_control_flow() == ControlFlow::Finished {
+            break;
+        }
         state.print_to_terminal()?;
     }
 
-    let results = state.stats.analysis_str("Press enter to continue");
+    let _ = history::record_session(&state.stats, state.session_type);
+    let history_summary = history::summary(state.stats.wpm(), &history::load_history());
+    let extra = if history_summary.is_empty() {
+        format!("Quotes by:\n{}\nPress enter to continue", state.attribution())
+    } else {
+        format!(
+            "{history_summary}\nQuotes by:\n{}\nPress enter to continue",
+            state.attribution()
+        )
+    };
+    let results = state.stats.analysis_str(&extra);
     clear_screen_and_print(out, &results, true)?;
-    // wait for user to press enter
-    loop {
-        let key_event = match read()? {
-            Event::Key(key) => key,
-            _ => continue,
+    // Wait for the user to press enter, reusing the same event channel as
+    // the typing loop above rather than a second raw `read()` — the
+    // blocking forwarder task is still alive and would otherwise race this
+    // loop for the next stdin keypress.
+    while let Some(event) = event_rx.recv().await {
+        let Event::Key(key_event) = event else {
+            continue;
         };
 
         if key_event.code == KeyCode::Enter {
@@ -132,7 +205,9 @@ pub struct TypingState<'a> {
     session_type: SessionType,
     quote_num: u32,
     out: &'a mut Stdout,
+    config: &'a Config,
     quote_chars: Vec<char>,
+    quotes: Vec<Quote>,
     typed_chars: Vec<char>,
     control_flow: ControlFlow,
     start_time: Instant,
@@ -151,12 +226,14 @@ enum ControlFlow {
 }
 
 impl<'a> TypingState<'a> {
-    fn new(session_type: SessionType, out: &'a mut Stdout, quote: Quote) -> Self {
+    fn new(session_type: SessionType, out: &'a mut Stdout, quote: Quote, config: &'a Config) -> Self {
         Self {
             session_type,
             out,
+            config,
             control_flow: ControlFlow::Normal,
             quote_chars: quote.content_chars(),
+            quotes: vec![quote],
             typed_chars: Vec::new(),
             quote_num: 1,
             start_time: Instant::now(),
@@ -193,6 +270,7 @@ impl<'a> TypingState<'a> {
             &self.quote_chars,
             &self.typed_chars,
             Some(self.stats),
+            &self.config.theme,
         )
     }
 
@@ -202,15 +280,29 @@ impl<'a> TypingState<'a> {
             return Ok(self.control_flow);
         }
 
-        match key_event.code {
+        let key = Key::new(key_event.code, key_event.modifiers);
+        match self.config.typing_keymap.get(&key) {
             // Exit on ctr-c and set control flow to exit
-            KeyCode::Char('c') if key_event.modifiers == KeyModifiers::CONTROL
-                => self.control_flow = ControlFlow::Exit,
-            // Print characters typed
-            KeyCode::Char(c) => self.typed_chars.push(c),
+            Some(Action::Exit) => self.control_flow = ControlFlow::Exit,
+            // Ctrl-W / Ctrl-Backspace: delete the word before the cursor
+            Some(Action::DeleteWord) => self.delete_word(),
+            // Ctrl-U: delete back to the start of the current line
+            Some(Action::DeleteLine) => self.delete_line(),
             // On backspace do some stuff
-            KeyCode::Backspace => { self.typed_chars.pop(); }
-            _ => (),
+            Some(Action::Backspace) => { self.typed_chars.pop(); }
+            // Keys with no typing-loop binding (including the mode-select
+            // letters s/m/t/z/q/c) are typed verbatim
+            _ => {
+                // Don't run ahead of the loaded quote: if the next quote is
+                // still being fetched (RequestsQuote/WaitingForQuote) this
+                // caps the overrun at zero instead of panicking later in
+                // `write_to_terminal`'s `quote_chars[typed_chars.len()..]`.
+                if let KeyCode::Char(c) = key_event.code {
+                    if self.typed_chars.len() < self.quote_chars.len() {
+                        self.typed_chars.push(c);
+                    }
+                }
+            }
         };
 
         self.stats.update(
@@ -223,12 +315,63 @@ impl<'a> TypingState<'a> {
         Ok(self.update_control_flow())
     }
 
+    /// Deletes the word before the cursor: trailing whitespace is dropped
+    /// first, then the contiguous run of non-whitespace characters up to
+    /// the previous whitespace boundary (or the start of the input).
+    fn delete_word(&mut self) {
+        while matches!(self.typed_chars.last(), Some(c) if c.is_whitespace()) {
+            self.typed_chars.pop();
+        }
+        while matches!(self.typed_chars.last(), Some(c) if !c.is_whitespace()) {
+            self.typed_chars.pop();
+        }
+    }
+
+    /// Deletes everything typed back to the start of the current quote's
+    /// most recent line break, or to the start of the current quote if it
+    /// has no line break before the cursor.
+    fn delete_line(&mut self) {
+        let cursor = self.typed_chars.len();
+        // `quote_chars` is every quote joined by a single space.
+        let (mut quote_start, mut offset) = (0, 0);
+        for quote in &self.quotes {
+            if offset > cursor {
+                break;
+            }
+            quote_start = offset;
+            offset += quote.content.chars().count() + 1;
+        }
+        let line_start = self.quote_chars[quote_start..cursor]
+            .iter()
+            .rposition(|&c| c == '\n')
+            .map_or(quote_start, |i| quote_start + i + 1);
+        self.typed_chars.truncate(line_start);
+    }
+
     fn add_quote(&mut self, quote: Quote) {
         self.control_flow = ControlFlow::Normal;
         self.quote_num += 1;
         // add new space
         self.quote_chars.push(' ');
         self.quote_chars.extend(quote.content_chars());
+        self.quotes.push(quote);
+    }
+
+    /// Renders the author and tags of every quote seen this session, with
+    /// the author name wrapped in a clickable hyperlink where the terminal
+    /// supports it.
+    fn attribution(&self) -> String {
+        self.quotes
+            .iter()
+            .map(|quote| {
+                if quote.tags.is_empty() {
+                    format!("  - {}", quote.author_link())
+                } else {
+                    format!("  - {} [{}]", quote.author_link(), quote.tags.join(", "))
+                }
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
     }
 
     /// Checks if another quote is needed to continue the session. It assumes